@@ -0,0 +1,51 @@
+// Rust Security Service - build metadata for GET /version
+// build.rs
+
+use std::process::Command;
+
+/// Runs `program` and returns its trimmed stdout, if it ran and succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (!stdout.is_empty()).then(|| stdout.to_string())
+}
+
+fn main() {
+    // Values already in the environment win, so image builds without a .git directory
+    // can pass them in as build args
+    let git_sha = std::env::var("HUGINN_GIT_SHA")
+        .ok()
+        .or_else(|| command_output("git", &["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // SOURCE_DATE_EPOCH keeps reproducible builds reproducible
+    let build_timestamp = std::env::var("HUGINN_BUILD_TIMESTAMP").ok().unwrap_or_else(|| {
+        let built_at = std::env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|seconds| seconds.parse::<i64>().ok())
+            .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+            .unwrap_or_else(chrono::Utc::now);
+        built_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    });
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = std::env::var("HUGINN_RUSTC_VERSION")
+        .ok()
+        .or_else(|| command_output(&rustc, &["--version"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=HUGINN_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=HUGINN_BUILD_TIMESTAMP={}", build_timestamp);
+    println!("cargo:rustc-env=HUGINN_RUSTC_VERSION={}", rustc_version);
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=HUGINN_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=HUGINN_BUILD_TIMESTAMP");
+    println!("cargo:rerun-if-env-changed=HUGINN_RUSTC_VERSION");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+}